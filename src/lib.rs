@@ -1,75 +1,481 @@
 use std::any::{Any, TypeId};
-use std::collections::HashMap;
-use std::sync::RwLock;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, RwLock};
 use once_cell::sync::Lazy;
 use std::sync::atomic::{AtomicU64, Ordering};
 
 type FilterCallback = Box<dyn Fn(Box<dyn Any>) -> Box<dyn Any> + Send + Sync>;
+type Teardown = Box<dyn FnOnce() + Send>;
 
 struct Filter {
     id: u64,
     priority: i32,
     callback: FilterCallback,
     type_id: TypeId,
+    teardown: Mutex<Option<Teardown>>,
 }
 
-static FILTERS: Lazy<RwLock<HashMap<String, Vec<Filter>>>> = Lazy::new(|| {
-    RwLock::new(HashMap::new())
-});
+/// Error returned by [`HookRegistry::try_apply_filters`] when a filter
+/// registered under a hook doesn't match the type the caller is applying.
+pub enum HookError<T> {
+    /// A filter registered under `hook` expects a different type than the
+    /// one being applied. `value` is the unmodified input, handed back so
+    /// the caller can recover instead of losing it to a panic.
+    TypeMismatch {
+        hook: String,
+        expected: TypeId,
+        found: TypeId,
+        value: T,
+    },
+}
+
+impl<T> std::fmt::Debug for HookError<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HookError::TypeMismatch { hook, expected, found, .. } => f
+                .debug_struct("TypeMismatch")
+                .field("hook", hook)
+                .field("expected", expected)
+                .field("found", found)
+                .finish(),
+        }
+    }
+}
+
+impl<T> std::fmt::Display for HookError<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HookError::TypeMismatch { hook, .. } => {
+                write!(f, "type mismatch for filter hook '{}'", hook)
+            }
+        }
+    }
+}
+
+impl<T> std::error::Error for HookError<T> {}
+
+impl<T: PartialEq> PartialEq for HookError<T> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                HookError::TypeMismatch { hook: h1, expected: e1, found: f1, value: v1 },
+                HookError::TypeMismatch { hook: h2, expected: e2, found: f2, value: v2 },
+            ) => h1 == h2 && e1 == e2 && f1 == f2 && v1 == v2,
+        }
+    }
+}
 
-static FILTER_ID_COUNTER: AtomicU64 = AtomicU64::new(1);
+/// An isolated set of filter hooks with its own id counter and namespace.
+///
+/// [`add_filter`], [`apply_filters`] and friends operate on a shared global
+/// instance; construct a `HookRegistry` directly when you need a sandboxed
+/// set of hooks, e.g. so tests or independent consumers don't collide on
+/// hook names.
+pub struct HookRegistry {
+    filters: RwLock<HashMap<String, Vec<Filter>>>,
+    next_id: AtomicU64,
+}
+
+impl HookRegistry {
+    /// Creates an empty registry with no filters registered.
+    pub fn new() -> Self {
+        HookRegistry {
+            filters: RwLock::new(HashMap::new()),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Registers a filter callback for the given hook name.
+    /// Returns an ID that can be used to remove the filter.
+    pub fn add_filter<T: 'static + Send + Sync>(
+        &self,
+        hook: &str,
+        priority: i32,
+        callback: impl Fn(T) -> T + 'static + Send + Sync,
+    ) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let filter = Filter {
+            id,
+            priority,
+            callback: Box::new(move |value: Box<dyn Any>| {
+                let value = *value.downcast::<T>().expect("Type mismatch in filter");
+                let new_value = callback(value);
+                Box::new(new_value)
+            }),
+            type_id: TypeId::of::<T>(),
+            teardown: Mutex::new(None),
+        };
+
+        let mut filters = self.filters.write().unwrap();
+        let entry = filters.entry(hook.to_string()).or_default();
+        entry.push(filter);
+        entry.sort_by_key(|f| f.priority);
+
+        id
+    }
+
+    /// Registers a filter that owns state initialized by `init` and torn
+    /// down by `teardown` when the filter is removed via [`HookRegistry::remove_filter`]
+    /// or [`HookRegistry::remove_all_filters`]. `callback` receives mutable access to the
+    /// state on every invocation, so it can accumulate across calls (counters,
+    /// caches, rate limiters) instead of being a pure function of its input.
+    pub fn add_stateful_filter<T, S>(
+        &self,
+        hook: &str,
+        priority: i32,
+        init: impl FnOnce() -> S,
+        callback: impl Fn(&mut S, T) -> T + 'static + Send + Sync,
+        teardown: impl FnOnce(S) + 'static + Send,
+    ) -> u64
+    where
+        T: 'static + Send + Sync,
+        S: 'static + Send,
+    {
+        let state = Arc::new(Mutex::new(Some(init())));
+        let state_for_callback = state.clone();
+
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let filter = Filter {
+            id,
+            priority,
+            callback: Box::new(move |value: Box<dyn Any>| {
+                let value = *value.downcast::<T>().expect("Type mismatch in filter");
+                let mut guard = state_for_callback.lock().unwrap();
+                let state = guard.as_mut().expect("Stateful filter state missing");
+                let new_value = callback(state, value);
+                Box::new(new_value)
+            }),
+            type_id: TypeId::of::<T>(),
+            teardown: Mutex::new(Some(Box::new(move || {
+                if let Some(state) = state.lock().unwrap().take() {
+                    teardown(state);
+                }
+            }))),
+        };
+
+        let mut filters = self.filters.write().unwrap();
+        let entry = filters.entry(hook.to_string()).or_default();
+        entry.push(filter);
+        entry.sort_by_key(|f| f.priority);
+
+        id
+    }
+
+    /// Applies all filter callbacks registered for the given hook to `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a filter registered under `hook` was registered for a
+    /// different type than `T`. Use [`HookRegistry::try_apply_filters`] to
+    /// handle that case without aborting.
+    pub fn apply_filters<T: 'static + Send + Sync>(&self, hook: &str, value: T) -> T {
+        self.apply_filters_checked(hook, value).0
+    }
 
-/// Registers a filter callback for the given hook name.
+    /// Applies all filter callbacks registered for the given hook to `value`,
+    /// also reporting whether any filter actually ran.
+    ///
+    /// # Panics
+    ///
+    /// Panics on a `TypeId` mismatch; see [`HookRegistry::apply_filters`].
+    pub fn apply_filters_checked<T: 'static + Send + Sync>(&self, hook: &str, value: T) -> (T, bool) {
+        match self.try_apply_filters_checked(hook, value) {
+            Ok(result) => result,
+            Err(HookError::TypeMismatch { hook, .. }) => {
+                panic!("Type mismatch for filter hook '{}'", hook)
+            }
+        }
+    }
+
+    /// Applies all filter callbacks registered for the given hook to `value`,
+    /// returning `Err(HookError::TypeMismatch)` instead of panicking if a
+    /// filter registered under `hook` doesn't match `T`. The unmodified input
+    /// is returned inside the error so callers can recover.
+    pub fn try_apply_filters<T: 'static + Send + Sync>(
+        &self,
+        hook: &str,
+        value: T,
+    ) -> Result<T, HookError<T>> {
+        self.try_apply_filters_checked(hook, value).map(|(value, _)| value)
+    }
+
+    fn try_apply_filters_checked<T: 'static + Send + Sync>(
+        &self,
+        hook: &str,
+        value: T,
+    ) -> Result<(T, bool), HookError<T>> {
+        let filters = self.filters.read().unwrap();
+        let filter_list = match filters.get(hook) {
+            Some(list) if !list.is_empty() => list,
+            _ => return Ok((value, false)),
+        };
+
+        if let Some(filter) = filter_list.iter().find(|f| f.type_id != TypeId::of::<T>()) {
+            return Err(HookError::TypeMismatch {
+                hook: hook.to_string(),
+                expected: TypeId::of::<T>(),
+                found: filter.type_id,
+                value,
+            });
+        }
+
+        let mut result: Box<dyn Any> = Box::new(value);
+        for filter in filter_list {
+            result = (filter.callback)(result);
+        }
+
+        Ok((*result.downcast::<T>().expect("Type mismatch in final value"), true))
+    }
+
+    /// Removes the filter with the specified ID from the given hook, running
+    /// its teardown callback if it was registered via [`HookRegistry::add_stateful_filter`].
+    /// Returns `true` if a filter was removed.
+    pub fn remove_filter(&self, hook: &str, id: u64) -> bool {
+        let removed = {
+            let mut filters = self.filters.write().unwrap();
+            filters.get_mut(hook).and_then(|list| {
+                let pos = list.iter().position(|f| f.id == id)?;
+                Some(list.remove(pos))
+            })
+        };
+
+        match removed {
+            Some(filter) => {
+                if let Some(teardown) = filter.teardown.into_inner().unwrap() {
+                    teardown();
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes all filters for the given hook, running the teardown callback
+    /// of any that were registered via [`HookRegistry::add_stateful_filter`].
+    pub fn remove_all_filters(&self, hook: &str) {
+        let removed = {
+            let mut filters = self.filters.write().unwrap();
+            filters.remove(hook)
+        };
+
+        if let Some(list) = removed {
+            for filter in list {
+                if let Some(teardown) = filter.teardown.into_inner().unwrap() {
+                    teardown();
+                }
+            }
+        }
+    }
+}
+
+impl Default for HookRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+static GLOBAL_REGISTRY: Lazy<HookRegistry> = Lazy::new(HookRegistry::new);
+
+/// Registers a filter callback for the given hook name on the global registry.
 /// Returns an ID that can be used to remove the filter.
 pub fn add_filter<T: 'static + Send + Sync>(
     hook: &str,
     priority: i32,
     callback: impl Fn(T) -> T + 'static + Send + Sync,
 ) -> u64 {
-    let id = FILTER_ID_COUNTER.fetch_add(1, Ordering::SeqCst);
-    let filter = Filter {
+    GLOBAL_REGISTRY.add_filter(hook, priority, callback)
+}
+
+/// Registers a stateful filter callback for the given hook name on the
+/// global registry. See [`HookRegistry::add_stateful_filter`] for details.
+pub fn add_stateful_filter<T, S>(
+    hook: &str,
+    priority: i32,
+    init: impl FnOnce() -> S,
+    callback: impl Fn(&mut S, T) -> T + 'static + Send + Sync,
+    teardown: impl FnOnce(S) + 'static + Send,
+) -> u64
+where
+    T: 'static + Send + Sync,
+    S: 'static + Send,
+{
+    GLOBAL_REGISTRY.add_stateful_filter(hook, priority, init, callback, teardown)
+}
+
+/// Applies all filter callbacks registered for the given hook to `value`
+/// on the global registry.
+pub fn apply_filters<T: 'static + Send + Sync>(hook: &str, value: T) -> T {
+    GLOBAL_REGISTRY.apply_filters(hook, value)
+}
+
+/// Applies all filter callbacks registered for the given hook to `value`
+/// on the global registry, also reporting whether any filter actually ran.
+pub fn apply_filters_checked<T: 'static + Send + Sync>(hook: &str, value: T) -> (T, bool) {
+    GLOBAL_REGISTRY.apply_filters_checked(hook, value)
+}
+
+/// Applies all filter callbacks registered for the given hook to `value` on
+/// the global registry, returning a `HookError` instead of panicking on a
+/// `TypeId` mismatch. See [`HookRegistry::try_apply_filters`].
+pub fn try_apply_filters<T: 'static + Send + Sync>(hook: &str, value: T) -> Result<T, HookError<T>> {
+    GLOBAL_REGISTRY.try_apply_filters(hook, value)
+}
+
+/// Removes the filter with the specified ID from the given hook on the
+/// global registry. Returns `true` if a filter was removed.
+pub fn remove_filter(hook: &str, id: u64) -> bool {
+    GLOBAL_REGISTRY.remove_filter(hook, id)
+}
+
+/// Removes all filters for the given hook on the global registry.
+pub fn remove_all_filters(hook: &str) {
+    GLOBAL_REGISTRY.remove_all_filters(hook)
+}
+
+type ActionCallback = Box<dyn Fn(&dyn Any) + Send + Sync>;
+
+struct Action {
+    id: u64,
+    priority: i32,
+    callback: ActionCallback,
+    type_id: TypeId,
+}
+
+static ACTIONS: Lazy<RwLock<HashMap<String, Vec<Action>>>> = Lazy::new(|| {
+    RwLock::new(HashMap::new())
+});
+
+static ACTION_ID_COUNTER: AtomicU64 = AtomicU64::new(1);
+
+/// Registers an action callback for the given hook name.
+/// Returns an ID that can be used to remove the action.
+pub fn add_action<T: 'static + Send + Sync>(
+    hook: &str,
+    priority: i32,
+    callback: impl Fn(&T) + 'static + Send + Sync,
+) -> u64 {
+    let id = ACTION_ID_COUNTER.fetch_add(1, Ordering::SeqCst);
+    let action = Action {
         id,
         priority,
-        callback: Box::new(move |value: Box<dyn Any>| {
-            let value = *value.downcast::<T>().expect("Type mismatch in filter");
-            let new_value = callback(value);
-            Box::new(new_value)
+        callback: Box::new(move |value: &dyn Any| {
+            let value = value.downcast_ref::<T>().expect("Type mismatch in action");
+            callback(value);
         }),
         type_id: TypeId::of::<T>(),
     };
 
-    let mut filters = FILTERS.write().unwrap();
-    let entry = filters.entry(hook.to_string()).or_insert_with(Vec::new);
-    entry.push(filter);
-    entry.sort_by_key(|f| f.priority);
+    let mut actions = ACTIONS.write().unwrap();
+    let entry = actions.entry(hook.to_string()).or_default();
+    entry.push(action);
+    entry.sort_by_key(|a| a.priority);
 
     id
 }
 
-/// Applies all filter callbacks registered for the given hook to `value`.
-pub fn apply_filters<T: 'static + Send + Sync>(hook: &str, value: T) -> T {
-    let filters = FILTERS.read().unwrap();
-    let filter_list = match filters.get(hook) {
+/// Runs all action callbacks registered for the given hook with `arg`.
+pub fn do_action<T: 'static + Send + Sync>(hook: &str, arg: &T) {
+    let actions = ACTIONS.read().unwrap();
+    let action_list = match actions.get(hook) {
         Some(list) => list,
-        None => return value,
+        None => return,
     };
 
-    let mut result: Box<dyn Any> = Box::new(value);
-    for filter in filter_list {
-        if filter.type_id == TypeId::of::<T>() {
-            result = (filter.callback)(result);
+    for action in action_list {
+        if action.type_id == TypeId::of::<T>() {
+            (action.callback)(arg);
         } else {
-            panic!("Type mismatch for filter hook '{}'", hook);
+            panic!("Type mismatch for action hook '{}'", hook);
         }
     }
+}
+
+/// Removes the action with the specified ID from the given hook.
+/// Returns `true` if an action was removed.
+pub fn remove_action(hook: &str, id: u64) -> bool {
+    let mut actions = ACTIONS.write().unwrap();
+    if let Some(list) = actions.get_mut(hook) {
+        let orig_len = list.len();
+        list.retain(|a| a.id != id);
+
+        return list.len() != orig_len;
+    }
+
+    false
+}
+
+/// Removes all actions for the given hook.
+pub fn remove_all_actions(hook: &str) {
+    let mut actions = ACTIONS.write().unwrap();
+    actions.remove(hook);
+}
+
+/// The outcome a control filter callback hands back to
+/// [`apply_filters_with_control`], deciding what happens next in the chain.
+pub enum FilterFlow<T> {
+    /// Pass `T` on to the next filter in the current hook's chain.
+    Continue(T),
+    /// Halt the chain immediately and return `T` as the final value.
+    Stop(T),
+    /// Abandon the current hook's chain and resume from the start of `to`'s chain.
+    Route { to: String, value: T },
+}
+
+enum ErasedFlow {
+    Continue(Box<dyn Any>),
+    Stop(Box<dyn Any>),
+    Route { to: String, value: Box<dyn Any> },
+}
+
+type ControlCallback = Box<dyn Fn(Box<dyn Any>) -> ErasedFlow + Send + Sync>;
+
+struct ControlFilter {
+    id: u64,
+    priority: i32,
+    callback: ControlCallback,
+    type_id: TypeId,
+}
+
+static CONTROL_FILTERS: Lazy<RwLock<HashMap<String, Vec<ControlFilter>>>> = Lazy::new(|| {
+    RwLock::new(HashMap::new())
+});
+
+static CONTROL_FILTER_ID_COUNTER: AtomicU64 = AtomicU64::new(1);
+
+/// Registers a control filter callback for the given hook name, for use with
+/// [`apply_filters_with_control`]. Returns an ID that can be used to remove the filter.
+pub fn add_control_filter<T: 'static + Send + Sync>(
+    hook: &str,
+    priority: i32,
+    callback: impl Fn(T) -> FilterFlow<T> + 'static + Send + Sync,
+) -> u64 {
+    let id = CONTROL_FILTER_ID_COUNTER.fetch_add(1, Ordering::SeqCst);
+    let filter = ControlFilter {
+        id,
+        priority,
+        callback: Box::new(move |value: Box<dyn Any>| {
+            let value = *value.downcast::<T>().expect("Type mismatch in control filter");
+            match callback(value) {
+                FilterFlow::Continue(v) => ErasedFlow::Continue(Box::new(v)),
+                FilterFlow::Stop(v) => ErasedFlow::Stop(Box::new(v)),
+                FilterFlow::Route { to, value } => ErasedFlow::Route { to, value: Box::new(value) },
+            }
+        }),
+        type_id: TypeId::of::<T>(),
+    };
+
+    let mut filters = CONTROL_FILTERS.write().unwrap();
+    let entry = filters.entry(hook.to_string()).or_default();
+    entry.push(filter);
+    entry.sort_by_key(|f| f.priority);
 
-    *result.downcast::<T>().expect("Type mismatch in final value")
+    id
 }
 
-/// Removes the filter with the specified ID from the given hook.
+/// Removes the control filter with the specified ID from the given hook.
 /// Returns `true` if a filter was removed.
-pub fn remove_filter(hook: &str, id: u64) -> bool {
-    let mut filters = FILTERS.write().unwrap();
+pub fn remove_control_filter(hook: &str, id: u64) -> bool {
+    let mut filters = CONTROL_FILTERS.write().unwrap();
     if let Some(list) = filters.get_mut(hook) {
         let orig_len = list.len();
         list.retain(|f| f.id != id);
@@ -80,12 +486,112 @@ pub fn remove_filter(hook: &str, id: u64) -> bool {
     false
 }
 
-/// Removes all filters for the given hook.
-pub fn remove_all_filters(hook: &str) {
-    let mut filters = FILTERS.write().unwrap();
+/// Removes all control filters for the given hook.
+pub fn remove_all_control_filters(hook: &str) {
+    let mut filters = CONTROL_FILTERS.write().unwrap();
     filters.remove(hook);
 }
 
+/// Applies control filters registered for `hook` to `value`, following
+/// `Route` hops to other hooks and stopping early on `Stop`. A hook visited
+/// twice during one call indicates a routing cycle; the value accumulated so
+/// far is returned instead of looping forever.
+pub fn apply_filters_with_control<T: 'static + Send + Sync>(hook: &str, value: T) -> T {
+    let mut current_hook = hook.to_string();
+    let mut current_value: Box<dyn Any> = Box::new(value);
+    let mut visited: HashSet<String> = HashSet::new();
+
+    loop {
+        if !visited.insert(current_hook.clone()) {
+            return *current_value.downcast::<T>().expect("Type mismatch in final value");
+        }
+
+        let filters = CONTROL_FILTERS.read().unwrap();
+        let filter_list = match filters.get(&current_hook) {
+            Some(list) => list,
+            None => return *current_value.downcast::<T>().expect("Type mismatch in final value"),
+        };
+
+        let mut routed_to = None;
+        for filter in filter_list {
+            if filter.type_id != TypeId::of::<T>() {
+                panic!("Type mismatch for filter hook '{}'", current_hook);
+            }
+
+            match (filter.callback)(current_value) {
+                ErasedFlow::Continue(v) => current_value = v,
+                ErasedFlow::Stop(v) => {
+                    return *v.downcast::<T>().expect("Type mismatch in final value");
+                }
+                ErasedFlow::Route { to, value } => {
+                    current_value = value;
+                    routed_to = Some(to);
+                    break;
+                }
+            }
+        }
+        drop(filters);
+
+        match routed_to {
+            Some(to) => current_hook = to,
+            None => return *current_value.downcast::<T>().expect("Type mismatch in final value"),
+        }
+    }
+}
+
+struct DefaultValue {
+    value: Box<dyn Any + Send + Sync>,
+    type_id: TypeId,
+}
+
+static DEFAULTS: Lazy<RwLock<HashMap<String, DefaultValue>>> = Lazy::new(|| {
+    RwLock::new(HashMap::new())
+});
+
+/// Registers a default value for `hook`, used by [`apply_filters_or_default`]
+/// as the value the hook's filters are applied to; it is returned unchanged
+/// when the hook has no filters. Replaces any existing default.
+pub fn set_default<T: 'static + Send + Sync>(hook: &str, value: T) {
+    let mut defaults = DEFAULTS.write().unwrap();
+    defaults.insert(
+        hook.to_string(),
+        DefaultValue {
+            value: Box::new(value),
+            type_id: TypeId::of::<T>(),
+        },
+    );
+}
+
+/// Removes the default value registered for `hook`, if any.
+pub fn clear_default(hook: &str) {
+    let mut defaults = DEFAULTS.write().unwrap();
+    defaults.remove(hook);
+}
+
+/// Applies the filters registered for `hook` to its registered default
+/// value and returns the result. Panics if no default has been registered
+/// for `hook` via [`set_default`].
+pub fn apply_filters_or_default<T: 'static + Clone + Send + Sync>(hook: &str) -> T {
+    let default = {
+        let defaults = DEFAULTS.read().unwrap();
+        let entry = defaults
+            .get(hook)
+            .unwrap_or_else(|| panic!("No default registered for hook '{}'", hook));
+
+        if entry.type_id != TypeId::of::<T>() {
+            panic!("Type mismatch for default on hook '{}'", hook);
+        }
+
+        entry
+            .value
+            .downcast_ref::<T>()
+            .expect("Type mismatch in default")
+            .clone()
+    };
+
+    apply_filters(hook, default)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -107,4 +613,258 @@ mod tests {
         let result = apply_filters(hook, "world".to_string());
         assert_eq!(result, "HELLO, WORLD");
     }
+
+    #[test]
+    fn test_actions_i32() {
+        use std::sync::atomic::AtomicI32;
+
+        let hook = "log_int";
+        let total = std::sync::Arc::new(AtomicI32::new(0));
+
+        let total_clone = total.clone();
+        add_action(hook, 10, move |v: &i32| {
+            total_clone.fetch_add(*v, Ordering::SeqCst);
+        });
+
+        let total_clone = total.clone();
+        add_action(hook, 20, move |v: &i32| {
+            total_clone.fetch_add(*v * 2, Ordering::SeqCst);
+        });
+
+        do_action(hook, &4);
+        assert_eq!(total.load(Ordering::SeqCst), 12);
+    }
+
+    #[test]
+    fn test_actions_remove() {
+        let hook = "notify_string";
+        let id = add_action(hook, 10, |_: &String| {});
+        assert!(remove_action(hook, id));
+        assert!(!remove_action(hook, id));
+    }
+
+    #[test]
+    fn test_control_filters_stop() {
+        let hook = "validate_int";
+        add_control_filter(hook, 10, |v: i32| {
+            if v < 0 {
+                FilterFlow::Stop(-1)
+            } else {
+                FilterFlow::Continue(v)
+            }
+        });
+        add_control_filter(hook, 20, |v: i32| FilterFlow::Continue(v * 10));
+
+        assert_eq!(apply_filters_with_control(hook, -5), -1);
+        assert_eq!(apply_filters_with_control(hook, 5), 50);
+    }
+
+    #[test]
+    fn test_control_filters_route() {
+        let entry_hook = "route_entry";
+        let error_hook = "route_error";
+
+        add_control_filter(entry_hook, 10, |_: i32| FilterFlow::Route {
+            to: "route_error".to_string(),
+            value: 99,
+        });
+        add_control_filter(error_hook, 10, |v: i32| FilterFlow::Continue(v + 1));
+
+        assert_eq!(apply_filters_with_control(entry_hook, 1), 100);
+    }
+
+    #[test]
+    fn test_control_filters_cycle_guard() {
+        let hook_a = "cycle_a";
+        let hook_b = "cycle_b";
+
+        add_control_filter(hook_a, 10, |v: i32| FilterFlow::Route {
+            to: "cycle_b".to_string(),
+            value: v + 1,
+        });
+        add_control_filter(hook_b, 10, |v: i32| FilterFlow::Route {
+            to: "cycle_a".to_string(),
+            value: v + 1,
+        });
+
+        // Each hook is visited once before the cycle is detected.
+        assert_eq!(apply_filters_with_control(hook_a, 0), 2);
+    }
+
+    #[test]
+    fn test_control_filters_remove() {
+        let hook = "removable_control_int";
+        let id = add_control_filter(hook, 10, |v: i32| FilterFlow::Continue(v + 1));
+
+        assert_eq!(apply_filters_with_control(hook, 1), 2);
+        assert!(remove_control_filter(hook, id));
+        assert!(!remove_control_filter(hook, id));
+        assert_eq!(apply_filters_with_control(hook, 1), 1);
+    }
+
+    #[test]
+    fn test_control_filters_remove_all() {
+        let hook = "removable_all_control_int";
+        add_control_filter(hook, 10, |v: i32| FilterFlow::Continue(v + 1));
+        add_control_filter(hook, 20, |v: i32| FilterFlow::Continue(v * 2));
+
+        remove_all_control_filters(hook);
+        assert_eq!(apply_filters_with_control(hook, 1), 1);
+    }
+
+    #[test]
+    fn test_apply_filters_checked() {
+        let hook = "checked_int";
+        let (value, ran) = apply_filters_checked(hook, 4);
+        assert_eq!(value, 4);
+        assert!(!ran);
+
+        add_filter(hook, 10, |v: i32| v + 1);
+        let (value, ran) = apply_filters_checked(hook, 4);
+        assert_eq!(value, 5);
+        assert!(ran);
+    }
+
+    #[test]
+    fn test_apply_filters_or_default() {
+        let hook = "default_int";
+        set_default(hook, 42);
+
+        assert_eq!(apply_filters_or_default::<i32>(hook), 42);
+
+        add_filter(hook, 10, |v: i32| v * 2);
+        assert_eq!(apply_filters_or_default::<i32>(hook), 84);
+
+        clear_default(hook);
+    }
+
+    #[test]
+    #[should_panic(expected = "No default registered")]
+    fn test_apply_filters_or_default_panics_without_default() {
+        apply_filters_or_default::<i32>("missing_default_int");
+    }
+
+    #[test]
+    fn test_hook_registry_is_isolated() {
+        // Registering on an independent registry must not be visible through
+        // the global free functions, even when the hook name collides.
+        let hook = "isolated_modify_int";
+        let registry = HookRegistry::new();
+        registry.add_filter(hook, 10, |v: i32| v - 1);
+
+        assert_eq!(registry.apply_filters(hook, 4), 3);
+        assert_eq!(apply_filters(hook, 4), 4);
+    }
+
+    #[test]
+    fn test_hook_registry_remove() {
+        let registry = HookRegistry::new();
+        let id = registry.add_filter("double", 10, |v: i32| v * 2);
+        assert_eq!(registry.apply_filters("double", 4), 8);
+
+        assert!(registry.remove_filter("double", id));
+        assert_eq!(registry.apply_filters("double", 4), 4);
+    }
+
+    #[test]
+    fn test_stateful_filter_accumulates() {
+        let registry = HookRegistry::new();
+        let hook = "counted_int";
+
+        registry.add_stateful_filter(
+            hook,
+            10,
+            || 0i32,
+            |count: &mut i32, v: i32| {
+                *count += 1;
+                v + *count
+            },
+            |_count| {},
+        );
+
+        assert_eq!(registry.apply_filters(hook, 10), 11);
+        assert_eq!(registry.apply_filters(hook, 10), 12);
+        assert_eq!(registry.apply_filters(hook, 10), 13);
+    }
+
+    #[test]
+    fn test_stateful_filter_teardown_on_remove() {
+        let registry = HookRegistry::new();
+        let hook = "torn_down_int";
+        let torn_down = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+
+        let torn_down_clone = torn_down.clone();
+        let id = registry.add_stateful_filter(
+            hook,
+            10,
+            || 0i32,
+            |count: &mut i32, v: i32| {
+                *count += 1;
+                v
+            },
+            move |_count| {
+                torn_down_clone.store(true, Ordering::SeqCst);
+            },
+        );
+
+        assert!(!torn_down.load(Ordering::SeqCst));
+        assert!(registry.remove_filter(hook, id));
+        assert!(torn_down.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_stateful_filter_teardown_on_remove_all() {
+        let registry = HookRegistry::new();
+        let hook = "torn_down_all_int";
+        let torn_down = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+
+        let torn_down_clone = torn_down.clone();
+        registry.add_stateful_filter(
+            hook,
+            10,
+            || 0i32,
+            |_count: &mut i32, v: i32| v,
+            move |_count| {
+                torn_down_clone.store(true, Ordering::SeqCst);
+            },
+        );
+
+        registry.remove_all_filters(hook);
+        assert!(torn_down.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_try_apply_filters_ok() {
+        let registry = HookRegistry::new();
+        let hook = "fallible_int";
+        registry.add_filter(hook, 10, |v: i32| v + 1);
+
+        assert_eq!(registry.try_apply_filters(hook, 4), Ok(5));
+        assert_eq!(registry.try_apply_filters::<i32>("unregistered_hook", 4), Ok(4));
+    }
+
+    #[test]
+    fn test_try_apply_filters_type_mismatch() {
+        let registry = HookRegistry::new();
+        let hook = "mixed_types";
+        registry.add_filter(hook, 10, |v: i32| v + 1);
+        registry.add_filter(hook, 20, |s: String| s.to_uppercase());
+
+        match registry.try_apply_filters::<i32>(hook, 4) {
+            Err(HookError::TypeMismatch { hook: mismatched_hook, value, .. }) => {
+                assert_eq!(mismatched_hook, hook);
+                assert_eq!(value, 4);
+            }
+            other => panic!("expected a type mismatch error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Type mismatch for filter hook")]
+    fn test_apply_filters_still_panics_on_mismatch() {
+        let registry = HookRegistry::new();
+        let hook = "mixed_types_panicking";
+        registry.add_filter(hook, 10, |s: String| s);
+        registry.apply_filters(hook, 4);
+    }
 }